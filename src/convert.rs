@@ -0,0 +1,231 @@
+//! Typed conversions between `Value` and native Rust types, so callers don't
+//! have to hand-walk a `Value` tree and downcast `HashMap`/`Vec` entries
+//! themselves: `let cfg: Config = parse(s)?.decode()?;`. This is the
+//! foundational surface a future `#[derive(FromJson)]` could target.
+
+use std::collections::HashMap;
+
+use crate::{error::JsonError, value::Value, Result};
+
+/// Converts a Rust value into its `Value` representation.
+pub trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+/// Converts a `Value` into a typed Rust value, failing with a
+/// `JsonError::TypeMismatch` if the shape doesn't match.
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self>;
+}
+
+impl Value {
+    /// Convenience wrapper around `FromJson::from_json` for chaining off of
+    /// `parse`, e.g. `let cfg: Config = parse(s)?.decode()?;`.
+    pub fn decode<T: FromJson>(&self) -> Result<T> {
+        T::from_json(self)
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> Value {
+        Value::Boolean(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::Boolean(b) => Ok(*b),
+            got => Err(type_mismatch("bool", got)),
+        }
+    }
+}
+
+macro_rules! impl_number_json {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> Value {
+                    Value::Number(*self as f64)
+                }
+            }
+
+            impl FromJson for $t {
+                fn from_json(value: &Value) -> Result<Self> {
+                    match value {
+                        Value::Number(n) => Ok(*n as $t),
+                        got => Err(type_mismatch(stringify!($t), got)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_number_json!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl ToJson for String {
+    fn to_json(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::String(s) => Ok(s.clone()),
+            got => Err(type_mismatch("String", got)),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        match self {
+            Some(value) => value.to_json(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => Ok(Some(T::from_json(value)?)),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::Array(items) => items.iter().map(T::from_json).collect(),
+            got => Err(type_mismatch("array", got)),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> Value {
+        Value::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::Object(fields) => fields
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), T::from_json(v)?)))
+                .collect(),
+            got => Err(type_mismatch("object", got)),
+        }
+    }
+}
+
+fn type_mismatch(expected: &str, got: &Value) -> JsonError {
+    JsonError::TypeMismatch {
+        expected: expected.to_string(),
+        got: kind_name(got).to_string(),
+    }
+}
+
+fn kind_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Boolean(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool_round_trip() {
+        assert_eq!(true.to_json(), Value::Boolean(true));
+        assert!(!bool::from_json(&Value::Boolean(false)).unwrap());
+        assert_eq!(
+            bool::from_json(&Value::Null).unwrap_err(),
+            JsonError::TypeMismatch {
+                expected: "bool".to_string(),
+                got: "null".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_number_round_trip() {
+        assert_eq!(42i32.to_json(), Value::Number(42.0));
+        assert_eq!(i32::from_json(&Value::Number(42.0)).unwrap(), 42);
+        assert_eq!(3.5f64.to_json(), Value::Number(3.5));
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        assert_eq!("hi".to_string().to_json(), Value::String("hi".to_string()));
+        assert_eq!(
+            String::from_json(&Value::String("hi".to_string())).unwrap(),
+            "hi".to_string()
+        );
+    }
+
+    #[test]
+    fn test_option_round_trip() {
+        assert_eq!(Some(1i32).to_json(), Value::Number(1.0));
+        assert_eq!(None::<i32>.to_json(), Value::Null);
+        assert_eq!(Option::<i32>::from_json(&Value::Null).unwrap(), None);
+        assert_eq!(
+            Option::<i32>::from_json(&Value::Number(1.0)).unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_vec_round_trip() {
+        let json = vec![1i32, 2, 3].to_json();
+        assert_eq!(
+            json,
+            Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(2.0),
+                Value::Number(3.0)
+            ])
+        );
+        assert_eq!(Vec::<i32>::from_json(&json).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_hashmap_round_trip() {
+        let map: HashMap<String, i32> = [("a".to_string(), 1)].into();
+        let json = map.to_json();
+        assert_eq!(
+            json,
+            Value::Object([("a".to_string(), Value::Number(1.0))].into())
+        );
+        assert_eq!(HashMap::<String, i32>::from_json(&json).unwrap(), map);
+    }
+
+    #[test]
+    fn test_decode_type_mismatch() {
+        let value = Value::String("not a number".to_string());
+        assert_eq!(
+            value.decode::<i32>().unwrap_err(),
+            JsonError::TypeMismatch {
+                expected: "i32".to_string(),
+                got: "string".to_string()
+            }
+        );
+    }
+}
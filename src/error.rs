@@ -17,5 +17,18 @@ pub enum JsonError {
         got: Option<char>,
     },
     UnexpectedToken(Option<Token>),
+    /// The structural validator found a token that isn't in the legal
+    /// follow-set for the current grammar state, e.g. a trailing comma, a
+    /// missing colon, or an unbalanced bracket.
+    InvalidTransition {
+        position: Position,
+        token: Option<Token>,
+    },
+    /// A `FromJson` conversion found a `Value` of the wrong shape, e.g. a
+    /// string where a number was expected, or an object missing a field.
+    TypeMismatch {
+        expected: String,
+        got: String,
+    },
     NoTokens,
 }
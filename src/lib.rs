@@ -1,6 +1,8 @@
+pub mod convert;
 pub mod error;
 pub mod parser;
 pub mod tokenizer;
+pub mod validator;
 pub mod value;
 
 pub type Result<T> = std::result::Result<T, error::JsonError>;
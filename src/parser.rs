@@ -3,12 +3,15 @@ use std::{collections::HashMap, iter::Peekable};
 use crate::{
     error::JsonError,
     tokenizer::{tokenize, Token},
+    validator::validate,
     value::Value,
     Result,
 };
 
 pub fn parse(input: &str) -> Result<Value> {
-    let mut tokens = tokenize(input)?.into_iter().peekable();
+    let tokens = tokenize(input)?;
+    validate(&tokens)?;
+    let mut tokens = tokens.into_iter().map(|(token, _)| token).peekable();
     parse_value(&mut tokens)
 }
 
@@ -57,7 +60,7 @@ where
                 continue;
             }
             t => match t {
-                Token::LeftBrace | Token::RightBrace | Token::LeftBracket | Token::Colon => {
+                Token::RightBrace | Token::Colon => {
                     return Err(JsonError::UnexpectedToken(Some(t.clone())))
                 }
                 _ => array.push(parse_value(tokens)?),
@@ -114,6 +117,8 @@ mod tests {
 
     #[test]
     fn test_parse_unexpected_token() {
+        // The validator rejects these before the recursive-descent parser
+        // ever sees them, since none of them can start a value.
         for token in [
             Token::RightBrace,
             Token::RightBracket,
@@ -123,7 +128,10 @@ mod tests {
             let input = token.to_string();
             assert_eq!(
                 parse(&input).unwrap_err(),
-                JsonError::UnexpectedToken(Some(token))
+                JsonError::InvalidTransition {
+                    position: (0, 1),
+                    token: Some(token)
+                }
             );
         }
     }
@@ -184,6 +192,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_array_of_nested_containers() {
+        // Regression test: `parse_array` used to only recurse into
+        // `parse_value` for its catch-all arm, rejecting `LeftBrace` and
+        // `LeftBracket` as array elements before ever reaching it.
+        assert_eq!(
+            parse("[1,[2,3]]").unwrap(),
+            Value::Array(vec![
+                Value::Number(1.0),
+                Value::Array(vec![Value::Number(2.0), Value::Number(3.0)])
+            ])
+        );
+        assert_eq!(
+            parse(r#"[{"a":1}]"#).unwrap(),
+            Value::Array(vec![Value::Object(
+                [("a".to_string(), Value::Number(1.0))].into()
+            )])
+        );
+        assert_eq!(
+            parse(r#"{"a":[{"b":1}]}"#).unwrap(),
+            Value::Object(
+                [(
+                    "a".to_string(),
+                    Value::Array(vec![Value::Object(
+                        [("b".to_string(), Value::Number(1.0))].into()
+                    )])
+                )]
+                .into()
+            )
+        );
+    }
+
     #[test]
     fn test_parse_object() {
         assert_eq!(
@@ -1,8 +1,11 @@
 use std::{fmt, iter::Peekable, str::Chars};
 
-use crate::{error::JsonError, Result};
+use crate::{
+    error::{JsonError, Position},
+    Result,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     LeftBrace,
     RightBrace,
@@ -17,67 +20,42 @@ pub enum Token {
     Null,
 }
 
-pub fn tokenize(input: &str) -> Result<Vec<Token>> {
+pub fn tokenize(input: &str) -> Result<Vec<(Token, Position)>> {
     let mut tokens = Vec::new();
     let mut chars = Indexer::new(input.chars().peekable());
     while let Some(c) = chars.next() {
-        match c {
-            '{' => tokens.push(Token::LeftBrace),
-            '}' => tokens.push(Token::RightBrace),
-            '[' => tokens.push(Token::LeftBracket),
-            ']' => tokens.push(Token::RightBracket),
-            '"' => {
-                let mut s = String::new();
-                while let Some(c) = chars.next() {
-                    if c == '"' {
-                        // end of string
-                        break;
-                    }
-                    s.push(c);
-                }
-                tokens.push(Token::String(s));
-            }
-            ',' => tokens.push(Token::Comma),
-            ':' => tokens.push(Token::Colon),
+        let position = (chars.row, chars.column);
+        let token = match c {
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '"' => Token::String(scan_string(&mut chars)?),
+            ',' => Token::Comma,
+            ':' => Token::Colon,
             't' => {
                 // expecting literal true
-                chars_match(&mut chars, Token::True)?;
-                tokens.push(Token::True);
+                chars_match(&mut chars, Token::True)?
             }
             'f' => {
                 // expecting literal false
-                chars_match(&mut chars, Token::False)?;
-                tokens.push(Token::False);
+                chars_match(&mut chars, Token::False)?
             }
             'n' => {
                 // expecting literal null
-                chars_match(&mut chars, Token::Null)?;
-                tokens.push(Token::Null);
-            }
-            c if c.is_digit(10) || c == '-' => {
-                static NUM_CHARS: &[char] = &['.', 'e', 'E', '+', '-'];
-                let mut num = String::new();
-                num.push(c);
-                while let Some(&c) = chars.chars.peek() {
-                    if c.is_digit(10) || NUM_CHARS.contains(&c) {
-                        num.push(c);
-                        chars.next();
-                    } else {
-                        break;
-                    }
-                }
-                let num = num.parse().unwrap();
-                tokens.push(Token::Number(num));
+                chars_match(&mut chars, Token::Null)?
             }
-            ' ' | '\n' => {}
+            c if c.is_ascii_digit() || c == '-' => Token::Number(scan_number(c, &mut chars)?),
+            ' ' | '\n' => continue,
             c => {
                 return Err(JsonError::UnexpectedCharacter {
-                    position: chars.position(),
+                    position,
                     expected_token: None,
                     got: Some(c),
                 })
             }
-        }
+        };
+        tokens.push((token, position));
     }
 
     Ok(tokens)
@@ -115,10 +93,6 @@ impl<'a> Indexer<'a> {
             column: 0,
         }
     }
-
-    fn position(&self) -> (usize, usize) {
-        (self.row, self.column)
-    }
 }
 
 impl<'a> Iterator for Indexer<'_> {
@@ -140,6 +114,231 @@ impl<'a> Iterator for Indexer<'_> {
     }
 }
 
+/// Scans the body of a string literal, having already consumed the opening
+/// `"`, decoding escape sequences as it goes. Returns an `UnexpectedCharacter`
+/// error (rather than panicking or silently truncating) on an invalid
+/// escape, a bad `\uXXXX` hex digit, a lone surrogate, or an unterminated
+/// string.
+fn scan_string(chars: &mut Indexer) -> Result<String> {
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('"') => s.push('"'),
+                Some('\\') => s.push('\\'),
+                Some('/') => s.push('/'),
+                Some('n') => s.push('\n'),
+                Some('t') => s.push('\t'),
+                Some('r') => s.push('\r'),
+                Some('b') => s.push('\u{8}'),
+                Some('f') => s.push('\u{c}'),
+                Some('u') => s.push(scan_unicode_escape(chars)?),
+                got => {
+                    return Err(JsonError::UnexpectedCharacter {
+                        position: (chars.row, chars.column),
+                        expected_token: None,
+                        got,
+                    })
+                }
+            },
+            Some(c) => s.push(c),
+            None => {
+                return Err(JsonError::UnexpectedCharacter {
+                    position: (chars.row, chars.column),
+                    expected_token: None,
+                    got: None,
+                })
+            }
+        }
+    }
+}
+
+/// Scans a `\uXXXX` escape (the `\u` having already been consumed), combining
+/// a high surrogate with a following `\uDC00`-`\uDFFF` low surrogate escape
+/// into a single `char`.
+fn scan_unicode_escape(chars: &mut Indexer) -> Result<char> {
+    let high = scan_hex4(chars)?;
+
+    if (0xD800..=0xDBFF).contains(&high) {
+        if chars.next() != Some('\\') || chars.next() != Some('u') {
+            return Err(JsonError::UnexpectedCharacter {
+                position: (chars.row, chars.column),
+                expected_token: None,
+                got: None,
+            });
+        }
+
+        let low = scan_hex4(chars)?;
+        if !(0xDC00..=0xDFFF).contains(&low) {
+            return Err(JsonError::UnexpectedCharacter {
+                position: (chars.row, chars.column),
+                expected_token: None,
+                got: None,
+            });
+        }
+
+        let code_point = 0x10000 + (high - 0xD800) * 0x400 + (low - 0xDC00);
+        return char::from_u32(code_point).ok_or(JsonError::UnexpectedCharacter {
+            position: (chars.row, chars.column),
+            expected_token: None,
+            got: None,
+        });
+    }
+
+    if (0xDC00..=0xDFFF).contains(&high) {
+        // a low surrogate with no preceding high surrogate
+        return Err(JsonError::UnexpectedCharacter {
+            position: (chars.row, chars.column),
+            expected_token: None,
+            got: None,
+        });
+    }
+
+    char::from_u32(high).ok_or(JsonError::UnexpectedCharacter {
+        position: (chars.row, chars.column),
+        expected_token: None,
+        got: None,
+    })
+}
+
+fn scan_hex4(chars: &mut Indexer) -> Result<u32> {
+    let mut value = 0u32;
+    for _ in 0..4 {
+        let got = chars.next();
+        let digit = got.and_then(|c| c.to_digit(16));
+        match digit {
+            Some(digit) => value = value * 16 + digit,
+            None => {
+                return Err(JsonError::UnexpectedCharacter {
+                    position: (chars.row, chars.column),
+                    expected_token: None,
+                    got,
+                })
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// Scans a JSON number (the first character `first`, a digit or `-`, having
+/// already been consumed), validating against the grammar `'-'? int frac?
+/// exp?` instead of greedily collecting `0-9 . e E + -` and letting
+/// `str::parse` panic on the result. Returns `JsonError::ParsingNumber` on
+/// any violation, including a leading zero, a trailing dot, or an empty
+/// exponent.
+fn scan_number(first: char, chars: &mut Indexer) -> Result<f64> {
+    let mut num = String::new();
+    num.push(first);
+
+    if first == '-' {
+        match chars.chars.peek() {
+            Some(&d) if d.is_ascii_digit() => {
+                chars.next();
+                num.push(d);
+                scan_int_rest(d, &mut num, chars)?;
+            }
+            _ => return Err(number_error(chars, "expected a digit after '-'")),
+        }
+    } else {
+        scan_int_rest(first, &mut num, chars)?;
+    }
+
+    scan_fraction(&mut num, chars)?;
+    scan_exponent(&mut num, chars)?;
+
+    let n: f64 = num
+        .parse()
+        .map_err(|_| number_error(chars, "not a valid number"))?;
+
+    if !n.is_finite() {
+        return Err(number_error(chars, "number is out of range"));
+    }
+
+    Ok(n)
+}
+
+/// Consumes the remaining digits of the integer part, given the first digit
+/// already scanned. A leading `0` may not be followed by another digit.
+fn scan_int_rest(first_digit: char, num: &mut String, chars: &mut Indexer) -> Result<()> {
+    if first_digit == '0' {
+        if matches!(chars.chars.peek(), Some(&next) if next.is_ascii_digit()) {
+            return Err(number_error(chars, "leading zeros are not allowed"));
+        }
+        return Ok(());
+    }
+
+    while let Some(&c) = chars.chars.peek() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Consumes an optional `.` followed by one or more digits.
+fn scan_fraction(num: &mut String, chars: &mut Indexer) -> Result<()> {
+    if chars.chars.peek() != Some(&'.') {
+        return Ok(());
+    }
+    num.push('.');
+    chars.next();
+
+    let mut digits = 0;
+    while let Some(&c) = chars.chars.peek() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            chars.next();
+            digits += 1;
+        } else {
+            break;
+        }
+    }
+    if digits == 0 {
+        return Err(number_error(chars, "expected a digit after '.'"));
+    }
+    Ok(())
+}
+
+/// Consumes an optional `e`/`E`, an optional sign, and one or more digits.
+fn scan_exponent(num: &mut String, chars: &mut Indexer) -> Result<()> {
+    match chars.chars.peek() {
+        Some(&'e') | Some(&'E') => {}
+        _ => return Ok(()),
+    }
+    let e = chars.next().unwrap();
+    num.push(e);
+
+    if matches!(chars.chars.peek(), Some(&('+' | '-'))) {
+        num.push(chars.next().unwrap());
+    }
+
+    let mut digits = 0;
+    while let Some(&c) = chars.chars.peek() {
+        if c.is_ascii_digit() {
+            num.push(c);
+            chars.next();
+            digits += 1;
+        } else {
+            break;
+        }
+    }
+    if digits == 0 {
+        return Err(number_error(chars, "expected a digit in the exponent"));
+    }
+    Ok(())
+}
+
+fn number_error(chars: &Indexer, message: &str) -> JsonError {
+    JsonError::ParsingNumber {
+        position: (chars.row, chars.column),
+        message: message.to_string(),
+    }
+}
+
 fn chars_match(chars: &mut Indexer, expected_token: Token) -> Result<Token> {
     for c in expected_token.to_string().chars().skip(1) {
         let got = chars.next();
@@ -189,7 +388,11 @@ mod tests {
 
     #[test]
     fn test_tokenize() {
-        let tokens = tokenize(COMPLETE_JSON).unwrap();
+        let tokens: Vec<Token> = tokenize(COMPLETE_JSON)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
         assert_eq!(
             tokens,
             vec![
@@ -302,4 +505,162 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_tokenize_positions() {
+        let tokens = tokenize("{\n  \"a\": 1\n}").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::LeftBrace, (0, 1)),
+                (Token::String("a".to_string()), (1, 3)),
+                (Token::Colon, (1, 6)),
+                (Token::Number(1.0), (1, 8)),
+                (Token::RightBrace, (2, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_string_escapes() {
+        let tokens: Vec<Token> = tokenize(r#""line\n\ttab \"quoted\" \\ \/ \b\f""#)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![Token::String(
+                "line\n\ttab \"quoted\" \\ / \u{8}\u{c}".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unicode_escape() {
+        let tokens: Vec<Token> = tokenize(r#""A\u00e9""#)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(tokens, vec![Token::String("A\u{e9}".to_string())]);
+    }
+
+    #[test]
+    fn test_tokenize_surrogate_pair_escape() {
+        // U+1F600 GRINNING FACE, encoded as a UTF-16 surrogate pair
+        let tokens: Vec<Token> = tokenize(r#""\uD83D\uDE00""#)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(tokens, vec![Token::String("\u{1F600}".to_string())]);
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_string() {
+        assert_eq!(
+            tokenize(r#""unterminated"#).unwrap_err(),
+            JsonError::UnexpectedCharacter {
+                position: (0, 13),
+                expected_token: None,
+                got: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_invalid_escape() {
+        assert_eq!(
+            tokenize(r#""bad\qescape""#).unwrap_err(),
+            JsonError::UnexpectedCharacter {
+                position: (0, 6),
+                expected_token: None,
+                got: Some('q')
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_lone_surrogate() {
+        assert_eq!(
+            tokenize(r#""\uD800""#).unwrap_err(),
+            JsonError::UnexpectedCharacter {
+                position: (0, 8),
+                expected_token: None,
+                got: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_double_dash() {
+        assert_eq!(
+            tokenize("--5").unwrap_err(),
+            JsonError::ParsingNumber {
+                position: (0, 1),
+                message: "expected a digit after '-'".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_empty_exponent() {
+        assert_eq!(
+            tokenize("1e").unwrap_err(),
+            JsonError::ParsingNumber {
+                position: (0, 2),
+                message: "expected a digit in the exponent".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_leading_zero() {
+        assert_eq!(
+            tokenize("012").unwrap_err(),
+            JsonError::ParsingNumber {
+                position: (0, 1),
+                message: "leading zeros are not allowed".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_trailing_dot() {
+        assert_eq!(
+            tokenize("1.").unwrap_err(),
+            JsonError::ParsingNumber {
+                position: (0, 2),
+                message: "expected a digit after '.'".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_out_of_range() {
+        // Grammar-valid but unrepresentable: `f64::parse` would otherwise
+        // silently saturate this to infinity.
+        assert_eq!(
+            tokenize("1e400").unwrap_err(),
+            JsonError::ParsingNumber {
+                position: (0, 5),
+                message: "number is out of range".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_number_does_not_panic_on_garbage() {
+        // `1.2.3` stops after scanning a valid `1.2`, then rejects the
+        // dangling `.3` as its own error instead of panicking.
+        assert_eq!(
+            tokenize("1.2.3").unwrap_err(),
+            JsonError::UnexpectedCharacter {
+                position: (0, 4),
+                expected_token: None,
+                got: Some('.')
+            }
+        );
+    }
 }
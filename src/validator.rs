@@ -0,0 +1,261 @@
+//! A streaming structural validator for token streams.
+//!
+//! `parse_array`/`parse_object` only loosely validate structure as they
+//! recurse, so malformed input like a trailing comma or a missing colon can
+//! slip through or surface as a confusing recursion-level error. `validate`
+//! walks the flat token stream once, tracking explicit stacks rather than
+//! relying on parser recursion depth, and rejects the first token that falls
+//! outside the legal follow-set for the current grammar state.
+
+use std::collections::HashSet;
+
+use crate::{
+    error::{JsonError, Position},
+    tokenizer::Token,
+    Result,
+};
+
+/// A token's shape, ignoring any payload, so it can be compared against the
+/// legal follow-set without needing an instance of the payload to match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    String,
+    Scalar,
+}
+
+impl From<&Token> for Kind {
+    fn from(token: &Token) -> Self {
+        match token {
+            Token::LeftBrace => Kind::LeftBrace,
+            Token::RightBrace => Kind::RightBrace,
+            Token::LeftBracket => Kind::LeftBracket,
+            Token::RightBracket => Kind::RightBracket,
+            Token::Comma => Kind::Comma,
+            Token::Colon => Kind::Colon,
+            Token::String(_) => Kind::String,
+            Token::Number(_) | Token::True | Token::False | Token::Null => Kind::Scalar,
+        }
+    }
+}
+
+/// Whether the next string token inside the innermost `{}` is a key or a
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseState {
+    Key,
+    Value,
+}
+
+/// Validates that `tokens` forms structurally well-formed JSON: balanced and
+/// correctly-nested brackets, a colon between every key and value, no
+/// trailing or missing commas, and nothing left over after a top-level
+/// value. On the first token that doesn't belong, returns
+/// `JsonError::InvalidTransition` carrying that token and its position.
+pub fn validate(tokens: &[(Token, Position)]) -> Result<()> {
+    if tokens.is_empty() {
+        return Err(JsonError::NoTokens);
+    }
+
+    let mut symbols: Vec<Token> = Vec::new();
+    let mut parse_state: Vec<ParseState> = Vec::new();
+    let mut next_maybe_symbols: HashSet<Kind> = value_start();
+
+    for (token, position) in tokens {
+        let kind = Kind::from(token);
+        if !next_maybe_symbols.contains(&kind) {
+            return Err(JsonError::InvalidTransition {
+                position: *position,
+                token: Some(token.clone()),
+            });
+        }
+
+        next_maybe_symbols = match token {
+            Token::LeftBrace => {
+                symbols.push(Token::LeftBrace);
+                parse_state.push(ParseState::Key);
+                [Kind::String, Kind::RightBrace].into()
+            }
+            Token::LeftBracket => {
+                symbols.push(Token::LeftBracket);
+                [
+                    Kind::LeftBrace,
+                    Kind::LeftBracket,
+                    Kind::String,
+                    Kind::Scalar,
+                    Kind::RightBracket,
+                ]
+                .into()
+            }
+            Token::RightBrace => {
+                match symbols.pop() {
+                    Some(Token::LeftBrace) => {}
+                    _ => {
+                        return Err(JsonError::InvalidTransition {
+                            position: *position,
+                            token: Some(token.clone()),
+                        })
+                    }
+                }
+                parse_state.pop();
+                after_value(&symbols)
+            }
+            Token::RightBracket => {
+                match symbols.pop() {
+                    Some(Token::LeftBracket) => {}
+                    _ => {
+                        return Err(JsonError::InvalidTransition {
+                            position: *position,
+                            token: Some(token.clone()),
+                        })
+                    }
+                }
+                after_value(&symbols)
+            }
+            Token::Colon => {
+                if let Some(state) = parse_state.last_mut() {
+                    *state = ParseState::Value;
+                }
+                value_start()
+            }
+            Token::Comma => match symbols.last() {
+                Some(Token::LeftBrace) => {
+                    if let Some(state) = parse_state.last_mut() {
+                        *state = ParseState::Key;
+                    }
+                    [Kind::String].into()
+                }
+                Some(Token::LeftBracket) => value_start(),
+                _ => unreachable!("comma is only ever legal inside an open container"),
+            },
+            Token::String(_) => match (symbols.last(), parse_state.last()) {
+                (Some(Token::LeftBrace), Some(ParseState::Key)) => [Kind::Colon].into(),
+                _ => after_value(&symbols),
+            },
+            Token::Number(_) | Token::True | Token::False | Token::Null => after_value(&symbols),
+        };
+    }
+
+    if let Some(unclosed) = symbols.last() {
+        let (_, position) = tokens.last().expect("validated at least one token");
+        return Err(JsonError::InvalidTransition {
+            position: *position,
+            token: Some(unclosed.clone()),
+        });
+    }
+
+    Ok(())
+}
+
+/// The legal first token of a value: a string, a number/bool/null, or the
+/// opening of a nested array or object.
+fn value_start() -> HashSet<Kind> {
+    [Kind::LeftBrace, Kind::LeftBracket, Kind::String, Kind::Scalar].into()
+}
+
+/// What may follow a complete value, given the innermost open container (or
+/// none, at the top level).
+fn after_value(symbols: &[Token]) -> HashSet<Kind> {
+    match symbols.last() {
+        Some(Token::LeftBrace) => [Kind::Comma, Kind::RightBrace].into(),
+        Some(Token::LeftBracket) => [Kind::Comma, Kind::RightBracket].into(),
+        Some(_) => unreachable!("only brace/bracket tokens are ever pushed onto symbols"),
+        None => HashSet::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::tokenize;
+    use crate::value::COMPLETE_JSON;
+
+    fn validate_str(input: &str) -> Result<()> {
+        validate(&tokenize(input).unwrap())
+    }
+
+    #[test]
+    fn test_validate_complete_json() {
+        assert_eq!(validate_str(COMPLETE_JSON), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_scalars() {
+        assert_eq!(validate_str("true"), Ok(()));
+        assert_eq!(validate_str("null"), Ok(()));
+        assert_eq!(validate_str(r#""hello""#), Ok(()));
+        assert_eq!(validate_str("1.5e3"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_trailing_comma() {
+        assert_eq!(
+            validate_str("[1, 2,]"),
+            Err(JsonError::InvalidTransition {
+                position: (0, 7),
+                token: Some(Token::RightBracket),
+            })
+        );
+        assert_eq!(
+            validate_str(r#"{"a": 1,}"#),
+            Err(JsonError::InvalidTransition {
+                position: (0, 9),
+                token: Some(Token::RightBrace),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_missing_colon() {
+        assert_eq!(
+            validate_str(r#"{"a" 1}"#),
+            Err(JsonError::InvalidTransition {
+                position: (0, 6),
+                token: Some(Token::Number(1.0)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_mismatched_brackets() {
+        assert_eq!(
+            validate_str("[1, 2}"),
+            Err(JsonError::InvalidTransition {
+                position: (0, 6),
+                token: Some(Token::RightBrace),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_unclosed_container() {
+        assert_eq!(
+            validate_str("[1, 2"),
+            Err(JsonError::InvalidTransition {
+                position: (0, 5),
+                token: Some(Token::LeftBracket),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_trailing_garbage() {
+        assert_eq!(
+            validate_str("true false"),
+            Err(JsonError::InvalidTransition {
+                position: (0, 6),
+                token: Some(Token::False),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_no_tokens() {
+        assert_eq!(validate(&[]), Err(JsonError::NoTokens));
+    }
+}
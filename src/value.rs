@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Value {
@@ -10,6 +10,131 @@ pub enum Value {
     Object(HashMap<String, Value>),
 }
 
+impl Value {
+    /// Renders `self` as pretty-printed JSON, indenting nested arrays and
+    /// objects by `indent` spaces per level.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        self.deparse(Some(indent), 0, false)
+    }
+
+    /// Like `to_string`, but escapes every non-ASCII character as `\uXXXX`
+    /// (a surrogate pair for codepoints outside the BMP) instead of passing
+    /// it through raw, for callers that need an ASCII-only payload.
+    pub fn to_string_ascii(&self) -> String {
+        self.deparse(None, 0, true)
+    }
+
+    /// Like `to_string_pretty`, but escapes non-ASCII characters as `\uXXXX`.
+    pub fn to_string_pretty_ascii(&self, indent: usize) -> String {
+        self.deparse(Some(indent), 0, true)
+    }
+
+    /// Walks the tree and emits JSON text, using `indent` to decide between
+    /// the compact form (`None`) and the pretty form (`Some(width)`), with
+    /// `depth` tracking how many containers deep the current value is for
+    /// indentation of nested values, and `ascii` selecting whether non-ASCII
+    /// characters are escaped as `\uXXXX` or passed through raw.
+    fn deparse(&self, indent: Option<usize>, depth: usize, ascii: bool) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Boolean(b) => b.to_string(),
+            // `NaN`/`Infinity` have no JSON representation; emit `null`
+            // rather than Rust's `to_string` text (e.g. "inf"), which isn't
+            // valid JSON and wouldn't round-trip through `parse`.
+            Value::Number(n) if !n.is_finite() => "null".to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => format!("\"{}\"", escape(s, ascii)),
+            Value::Array(array) => deparse_items(
+                indent,
+                depth,
+                '[',
+                ']',
+                array.iter().map(|v| {
+                    let value = v.deparse(indent, depth + 1, ascii);
+                    (None, value)
+                }),
+            ),
+            Value::Object(object) => deparse_items(
+                indent,
+                depth,
+                '{',
+                '}',
+                object
+                    .iter()
+                    .map(|(k, v)| (Some(escape(k, ascii)), v.deparse(indent, depth + 1, ascii))),
+            ),
+        }
+    }
+}
+
+fn deparse_items(
+    indent: Option<usize>,
+    depth: usize,
+    open: char,
+    close: char,
+    items: impl ExactSizeIterator<Item = (Option<String>, String)>,
+) -> String {
+    if items.len() == 0 {
+        return format!("{open}{close}");
+    }
+
+    let colon = if indent.is_some() { ": " } else { ":" };
+    let entry = |key: Option<String>, value: String| match key {
+        Some(key) => format!("\"{key}\"{colon}{value}"),
+        None => value,
+    };
+
+    match indent {
+        Some(width) => {
+            let pad = " ".repeat(width * (depth + 1));
+            let closing_pad = " ".repeat(width * depth);
+            let body: Vec<String> = items
+                .map(|(key, value)| format!("{pad}{}", entry(key, value)))
+                .collect();
+            format!("{open}\n{}\n{closing_pad}{close}", body.join(",\n"))
+        }
+        None => {
+            let body: Vec<String> = items.map(|(key, value)| entry(key, value)).collect();
+            format!("{open}{}{close}", body.join(","))
+        }
+    }
+}
+
+/// Re-escapes a decoded string, the inverse of the unescaping done by
+/// `tokenizer::tokenize`: `"`, `\`, and control characters U+0000-U+001F are
+/// always escaped. Non-ASCII text passes through as-is, since JSON strings are
+/// valid UTF-8, unless `ascii` is set, in which case it's escaped as
+/// `\uXXXX` (a surrogate pair for codepoints outside the BMP).
+fn escape(s: &str, ascii: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if ascii && !c.is_ascii() => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deparse(None, 0, false))
+    }
+}
+
 #[cfg(test)]
 pub(crate) static COMPLETE_JSON: &str = r#"{
     "string": "This is a string",
@@ -38,3 +163,82 @@ pub(crate) static COMPLETE_JSON: &str = r#"{
     "emptyArray": [],
     "emptyObject": {}
 }"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string_scalars() {
+        assert_eq!(Value::Null.to_string(), "null");
+        assert_eq!(Value::Boolean(true).to_string(), "true");
+        assert_eq!(Value::Number(1.1e3).to_string(), "1100");
+        assert_eq!(
+            Value::String("a \"quoted\"\nstring".to_string()).to_string(),
+            r#""a \"quoted\"\nstring""#
+        );
+    }
+
+    #[test]
+    fn test_to_string_compact() {
+        let array = Value::Array(vec![Value::Number(1.0), Value::Null, Value::Boolean(false)]);
+        assert_eq!(array.to_string(), "[1,null,false]");
+        assert_eq!(Value::Array(vec![]).to_string(), "[]");
+
+        let object = Value::Object([("key".to_string(), Value::Number(1.0))].into());
+        assert_eq!(object.to_string(), r#"{"key":1}"#);
+        assert_eq!(Value::Object(Default::default()).to_string(), "{}");
+    }
+
+    #[test]
+    fn test_to_string_pretty() {
+        let array = Value::Array(vec![Value::Number(1.0), Value::Number(2.0)]);
+        assert_eq!(array.to_string_pretty(2), "[\n  1,\n  2\n]");
+
+        let object = Value::Object([("key".to_string(), Value::Array(vec![Value::Null]))].into());
+        assert_eq!(
+            object.to_string_pretty(2),
+            "{\n  \"key\": [\n    null\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_to_string_non_finite_number() {
+        assert_eq!(Value::Number(f64::NAN).to_string(), "null");
+        assert_eq!(Value::Number(f64::INFINITY).to_string(), "null");
+        assert_eq!(Value::Number(f64::NEG_INFINITY).to_string(), "null");
+    }
+
+    #[test]
+    fn test_to_string_ascii() {
+        assert_eq!(
+            Value::String("caf\u{e9}".to_string()).to_string_ascii(),
+            r#""caf\u00e9""#
+        );
+        // U+1F600 GRINNING FACE, outside the BMP, escapes as a surrogate pair.
+        assert_eq!(
+            Value::String("\u{1F600}".to_string()).to_string_ascii(),
+            r#""\ud83d\ude00""#
+        );
+        assert_eq!(
+            Value::String("caf\u{e9}".to_string()).to_string(),
+            "\"caf\u{e9}\""
+        );
+    }
+
+    #[test]
+    fn test_to_string_pretty_ascii() {
+        let array = Value::Array(vec![Value::String("\u{e9}".to_string())]);
+        assert_eq!(array.to_string_pretty_ascii(2), "[\n  \"\\u00e9\"\n]");
+    }
+
+    #[test]
+    fn test_to_string_round_trip() {
+        let value = crate::parser::parse(COMPLETE_JSON).unwrap();
+        let reparsed = crate::parser::parse(&value.to_string()).unwrap();
+        assert_eq!(value, reparsed);
+
+        let reparsed_pretty = crate::parser::parse(&value.to_string_pretty(4)).unwrap();
+        assert_eq!(value, reparsed_pretty);
+    }
+}